@@ -1,6 +1,6 @@
 extern crate alloc;
 
-use alloc::{ffi::CString, vec::Vec};
+use alloc::{ffi::CString, string::String, vec::Vec};
 
 use bytemuck::{AnyBitPattern, Pod, bytes_of, zeroed};
 
@@ -27,6 +27,22 @@ pub fn vm_load<T: AnyBitPattern>(ptr: *const T, len: usize) -> VmResult<Vec<T>>
     unsafe { vm_load_any(ptr, len) }
 }
 
+/// Loads a vector of at most `max_len` elements from the virtual memory.
+///
+/// This is meant for user-controlled lengths, e.g. the instruction count of a
+/// program buffer, where `len` must be checked before any allocation happens.
+/// Returns [`VmError::TooLong`] if `len` exceeds `max_len`.
+pub fn vm_load_bounded<T: AnyBitPattern>(
+    ptr: *const T,
+    len: usize,
+    max_len: usize,
+) -> VmResult<Vec<T>> {
+    if len > max_len {
+        return Err(VmError::TooLong);
+    }
+    vm_load(ptr, len)
+}
+
 #[inline]
 fn is_zero<T: Pod>(value: &T) -> bool {
     bytes_of(value) == bytes_of(&zeroed::<T>())
@@ -36,6 +52,13 @@ const MAX_BYTES: usize = 131072;
 
 /// Loads elements from the given pointer until a zero element is found.
 pub fn vm_load_until_nul<T: Pod>(ptr: *const T) -> VmResult<Vec<T>> {
+    vm_load_until_nul_bounded(ptr, (MAX_BYTES / size_of::<T>()).saturating_sub(1))
+}
+
+/// Loads elements from the given pointer until a zero element is found,
+/// returning [`VmError::TooLong`] if more than `max_len` non-zero elements
+/// precede it.
+pub fn vm_load_until_nul_bounded<T: Pod>(ptr: *const T, max_len: usize) -> VmResult<Vec<T>> {
     if !ptr.is_aligned() {
         return Err(VmError::BadAddress);
     }
@@ -47,8 +70,18 @@ pub fn vm_load_until_nul<T: Pod>(ptr: *const T) -> VmResult<Vec<T>> {
     loop {
         const CHUNK_SIZE: usize = 4096; // 4 KiB
 
-        let start = ptr.addr() + result.len() * size;
-        let end = (start + 1).next_multiple_of(CHUNK_SIZE);
+        let start = result
+            .len()
+            .checked_mul(size)
+            .and_then(|offset| ptr.addr().checked_add(offset))
+            .ok_or(VmError::BadAddress)?;
+        // Read up to the next chunk boundary, but always at least one element.
+        let min_end = start.checked_add(size).ok_or(VmError::BadAddress)?;
+        let end = start
+            .checked_add(1)
+            .and_then(|start| start.checked_next_multiple_of(CHUNK_SIZE))
+            .unwrap_or(usize::MAX)
+            .max(min_end);
         let len = (end - start) / size;
 
         result.reserve(len);
@@ -60,7 +93,7 @@ pub fn vm_load_until_nul<T: Pod>(ptr: *const T) -> VmResult<Vec<T>> {
         let pos = buf.iter().position(is_zero);
 
         unsafe { result.set_len(result.len() + pos.unwrap_or(len)) };
-        if result.len() >= MAX_BYTES / size {
+        if result.len() > max_len {
             return Err(VmError::TooLong);
         }
 
@@ -79,3 +112,48 @@ pub fn vm_load_c_string(ptr: *const u8) -> VmResult<CString> {
     // SAFETY: vm_load_until_nul guarantees no interior 0 byte.
     Ok(unsafe { CString::from_vec_unchecked(bytes) })
 }
+
+/// Loads a null-terminated C string of at most `max_len` bytes (excluding the
+/// terminator) from the virtual memory.
+pub fn vm_load_c_string_bounded(ptr: *const u8, max_len: usize) -> VmResult<CString> {
+    let bytes = vm_load_until_nul_bounded(ptr, max_len)?;
+    // SAFETY: vm_load_until_nul_bounded guarantees no interior 0 byte.
+    Ok(unsafe { CString::from_vec_unchecked(bytes) })
+}
+
+/// Loads a null-terminated UTF-8 string from the virtual memory.
+///
+/// Returns [`VmError::InvalidInput`] if the string is not valid UTF-8.
+pub fn vm_load_string(ptr: *const u8) -> VmResult<String> {
+    let bytes = vm_load_until_nul(ptr)?;
+    String::from_utf8(bytes).map_err(|_| VmError::InvalidInput)
+}
+
+/// Loads a null-terminated array of C string pointers from the virtual memory,
+/// e.g. `argv` or `envp` of `execve`.
+///
+/// Returns [`VmError::TooLong`] if a single string is longer than `max_len`
+/// bytes (excluding the terminator), or if the strings, including their
+/// terminators, take more than `max_bytes` bytes in total.
+pub fn vm_load_c_string_array(
+    ptr: *const *const u8,
+    max_len: usize,
+    max_bytes: usize,
+) -> VmResult<Vec<CString>> {
+    // Every string takes at least one byte, so there are at most `max_bytes`
+    // of them.
+    let ptrs = vm_load_until_nul_bounded(ptr.cast::<usize>(), max_bytes)?;
+
+    let mut result = Vec::with_capacity(ptrs.len());
+    let mut remaining = max_bytes;
+    for ptr in ptrs {
+        let limit = remaining
+            .checked_sub(1)
+            .ok_or(VmError::TooLong)?
+            .min(max_len);
+        let string = vm_load_c_string_bounded(ptr as *const u8, limit)?;
+        remaining -= string.as_bytes_with_nul().len();
+        result.push(string);
+    }
+    Ok(result)
+}
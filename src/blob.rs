@@ -0,0 +1,28 @@
+use core::mem::MaybeUninit;
+
+use crate::{VmError, VmImpl, VmIo, VmResult, check_range};
+
+/// Reads an opaque blob from the virtual memory into `buf`, e.g. the
+/// architecture-specific extended FPU state in a signal frame.
+///
+/// `ptr` must be aligned to `align`, as required by the architecture for the
+/// blob.
+pub fn vm_read_blob(ptr: *const u8, align: usize, buf: &mut [MaybeUninit<u8>]) -> VmResult {
+    if !ptr.addr().is_multiple_of(align) {
+        return Err(VmError::BadAddress);
+    }
+    check_range(ptr.addr(), buf.len())?;
+    VmImpl::new().read(ptr.addr(), buf)
+}
+
+/// Writes an opaque blob from `buf` to the virtual memory.
+///
+/// `ptr` must be aligned to `align`, as required by the architecture for the
+/// blob.
+pub fn vm_write_blob(ptr: *mut u8, align: usize, buf: &[u8]) -> VmResult {
+    if !ptr.addr().is_multiple_of(align) {
+        return Err(VmError::BadAddress);
+    }
+    check_range(ptr.addr(), buf.len())?;
+    VmImpl::new().write(ptr.addr(), buf)
+}
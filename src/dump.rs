@@ -0,0 +1,69 @@
+use core::{fmt, mem::MaybeUninit, slice};
+
+use crate::{VmImpl, VmIo, check_range};
+
+const BYTES_PER_LINE: usize = 16;
+
+/// Writes a classic hexdump of `len` bytes of the virtual memory starting at
+/// `start` to `w`.
+///
+/// Bytes that cannot be read are shown as `??`, so a dump spanning unmapped
+/// memory still prints everything that is accessible. The dump never wraps
+/// around the end of the address space: bytes past it are shown as `??` and
+/// no further lines are printed.
+pub fn vm_dump_hex<W: fmt::Write + ?Sized>(start: usize, len: usize, w: &mut W) -> fmt::Result {
+    let mut vm = VmImpl::new();
+
+    for offset in (0..len).step_by(BYTES_PER_LINE) {
+        let Some(addr) = start.checked_add(offset) else {
+            break;
+        };
+        let count = (len - offset).min(BYTES_PER_LINE);
+
+        let mut line = [None; BYTES_PER_LINE];
+        let mut buf = [MaybeUninit::uninit(); BYTES_PER_LINE];
+        if check_range(addr, count).is_ok() && vm.read(addr, &mut buf[..count]).is_ok() {
+            for (byte, value) in line.iter_mut().zip(&buf[..count]) {
+                // SAFETY: The read above initialized `count` bytes.
+                *byte = Some(unsafe { value.assume_init() });
+            }
+        } else {
+            // Fall back to reading byte by byte to find the holes.
+            for (i, byte) in line[..count].iter_mut().enumerate() {
+                let Some(addr) = addr.checked_add(i) else {
+                    break;
+                };
+                let mut value = MaybeUninit::uninit();
+                if check_range(addr, 1).is_ok()
+                    && vm.read(addr, slice::from_mut(&mut value)).is_ok()
+                {
+                    // SAFETY: The read above initialized the byte.
+                    *byte = Some(unsafe { value.assume_init() });
+                }
+            }
+        }
+
+        write!(w, "{addr:016x} ")?;
+        for (i, byte) in line.iter().enumerate() {
+            if i % 8 == 0 {
+                w.write_char(' ')?;
+            }
+            match byte {
+                _ if i >= count => w.write_str("   ")?,
+                Some(byte) => write!(w, "{byte:02x} ")?,
+                None => w.write_str("?? ")?,
+            }
+        }
+        w.write_str(" |")?;
+        for byte in &line[..count] {
+            let ch = match byte {
+                Some(byte) if byte.is_ascii_graphic() || *byte == b' ' => *byte as char,
+                Some(_) => '.',
+                None => '?',
+            };
+            w.write_char(ch)?;
+        }
+        w.write_str("|\n")?;
+    }
+    Ok(())
+}
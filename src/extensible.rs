@@ -0,0 +1,78 @@
+use core::mem::MaybeUninit;
+
+use bytemuck::{AnyBitPattern, NoUninit, bytes_of};
+
+use crate::{VmError, VmImpl, VmIo, VmResult, check_range};
+
+/// The maximum size of an extensible structure in the virtual memory, matching
+/// the page-size limit Linux applies in `clone3`, `openat2` and
+/// `sched_setattr`.
+pub const MAX_EXTENSIBLE_SIZE: usize = 4096;
+
+/// Reads an extensible structure whose size in the virtual memory is `size`,
+/// as used by syscalls like `clone3`, `openat2` and `sched_setattr`.
+///
+/// - If `size` is smaller than `min_size`, typically the size of the first
+///   published version of the structure, [`VmError::InvalidInput`] is returned.
+/// - If `size` is larger than [`MAX_EXTENSIBLE_SIZE`], [`VmError::TooLong`] is
+///   returned.
+/// - If `size` is smaller than `T`, the missing tail is zero-filled.
+/// - If `size` is larger than `T`, the unknown tail must be all zeros,
+///   otherwise [`VmError::TooLong`] is returned.
+pub fn vm_read_extensible<T: AnyBitPattern>(
+    ptr: *const T,
+    size: usize,
+    min_size: usize,
+) -> VmResult<T> {
+    if size < min_size {
+        return Err(VmError::InvalidInput);
+    }
+    if size > MAX_EXTENSIBLE_SIZE {
+        return Err(VmError::TooLong);
+    }
+    if !ptr.is_aligned() {
+        return Err(VmError::BadAddress);
+    }
+    check_range(ptr.addr(), size)?;
+    let end = ptr.addr() + size;
+
+    let mut vm = VmImpl::new();
+    let mut value = MaybeUninit::<T>::zeroed();
+    let len = size.min(size_of::<T>());
+    vm.read(ptr.addr(), &mut value.as_bytes_mut()[..len])?;
+
+    let mut start = ptr.addr() + len;
+    while start < end {
+        const CHUNK_SIZE: usize = 64;
+
+        let mut buf = [MaybeUninit::uninit(); CHUNK_SIZE];
+        let buf = &mut buf[..(end - start).min(CHUNK_SIZE)];
+        vm.read(start, buf)?;
+        // SAFETY: The read above initialized the buffer.
+        if unsafe { buf.assume_init_ref() }.iter().any(|&b| b != 0) {
+            return Err(VmError::TooLong);
+        }
+        start += buf.len();
+    }
+
+    // SAFETY: `AnyBitPattern`, and the value is fully initialized since it
+    // started zeroed.
+    Ok(unsafe { value.assume_init() })
+}
+
+/// Writes an extensible structure to the virtual memory where the user has
+/// room for `size` bytes, as used by interfaces like `statx` and `getsockopt`.
+///
+/// At most `size` bytes of `value` are written, and nothing beyond them.
+/// Returns whether the structure was truncated, i.e. `size` is smaller than
+/// `T`.
+pub fn vm_write_extensible<T: NoUninit>(ptr: *mut T, size: usize, value: T) -> VmResult<bool> {
+    if !ptr.is_aligned() {
+        return Err(VmError::BadAddress);
+    }
+    let bytes = bytes_of(&value);
+    let len = size.min(bytes.len());
+    check_range(ptr.addr(), len)?;
+    VmImpl::new().write(ptr.addr(), &bytes[..len])?;
+    Ok(len < bytes.len())
+}
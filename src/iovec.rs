@@ -0,0 +1,82 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{ops::Deref, slice};
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::{VmError, VmResult, vm_load};
+
+/// The maximum number of segments in an I/O vector.
+pub const UIO_MAXIOV: usize = 1024;
+
+/// A segment of a vectored I/O buffer, i.e. `struct iovec`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoVec {
+    /// The start address of the segment.
+    pub base: usize,
+    /// The length of the segment in bytes.
+    pub len: usize,
+}
+
+// SAFETY: `IoVec` is `repr(C)` with two `usize` fields and no padding.
+unsafe impl Zeroable for IoVec {}
+// SAFETY: See above.
+unsafe impl Pod for IoVec {}
+
+/// An I/O vector loaded from the virtual memory, as used by `readv`, `writev`
+/// and `sendmsg`.
+///
+/// Dereferences to the loaded segments.
+pub struct VmIoVecs {
+    iovs: Vec<IoVec>,
+    total_len: usize,
+}
+
+impl VmIoVecs {
+    /// Loads `iovcnt` segments from the virtual memory starting at `ptr`.
+    ///
+    /// Returns [`VmError::InvalidInput`] if `iovcnt` exceeds [`UIO_MAXIOV`] or
+    /// the total length overflows `isize`, and [`VmError::BadAddress`] if a
+    /// segment wraps around the address space.
+    pub fn load(ptr: *const IoVec, iovcnt: usize) -> VmResult<Self> {
+        if iovcnt > UIO_MAXIOV {
+            return Err(VmError::InvalidInput);
+        }
+        let iovs = vm_load(ptr, iovcnt)?;
+
+        let mut total_len = 0usize;
+        for iov in &iovs {
+            iov.base.checked_add(iov.len).ok_or(VmError::BadAddress)?;
+            total_len = total_len
+                .checked_add(iov.len)
+                .filter(|&len| len <= isize::MAX as usize)
+                .ok_or(VmError::InvalidInput)?;
+        }
+
+        Ok(Self { iovs, total_len })
+    }
+
+    /// Returns the total length of all segments in bytes.
+    pub fn total_len(&self) -> usize {
+        self.total_len
+    }
+}
+
+impl Deref for VmIoVecs {
+    type Target = [IoVec];
+
+    fn deref(&self) -> &[IoVec] {
+        &self.iovs
+    }
+}
+
+impl<'a> IntoIterator for &'a VmIoVecs {
+    type IntoIter = slice::Iter<'a, IoVec>;
+    type Item = &'a IoVec;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iovs.iter()
+    }
+}
@@ -1,12 +1,12 @@
 //! Virtual memory utilities.
 #![no_std]
 #![feature(maybe_uninit_as_bytes)]
-#![feature(maybe_uninit_slice)]
 #![warn(missing_docs)]
 
-use core::{mem::MaybeUninit, slice};
+use core::mem::MaybeUninit;
 
 use axerrno::LinuxError;
+use bytemuck::{NoUninit, cast_slice};
 use extern_trait::extern_trait;
 
 /// Errors that can occur during virtual memory operations.
@@ -17,20 +17,32 @@ pub enum VmError {
     BadAddress,
     /// The operation is not allowed, e.g., trying to write to read-only memory.
     AccessDenied,
+    /// The address is mapped but has no backing storage, e.g., a file-backed
+    /// mapping beyond the end of the file.
+    ///
+    /// This is reported as `EFAULT` to syscalls, but the kernel may want to
+    /// raise `SIGBUS` instead of `SIGSEGV` for it.
+    BusError,
     /// The C-style string or array is too long.
     ///
-    /// This error is returned by [`vm_load_c_string`] and [`vm_load_until_nul`]
-    /// when the null terminator is not found within a predefined search limit.
-    #[cfg(feature = "alloc")]
+    /// This error is returned by `vm_load_c_string` and `vm_load_until_nul`
+    /// (and their `_bounded` variants) when the null terminator is not found
+    /// within the search limit, and by [`vm_read_extensible`] when the
+    /// structure is larger than [`MAX_EXTENSIBLE_SIZE`] or has unknown non-zero
+    /// trailing bytes.
     TooLong,
+    /// The input is invalid, e.g., a string that is not valid UTF-8, an I/O
+    /// vector with too many segments, or an extensible structure smaller than
+    /// its minimum size.
+    InvalidInput,
 }
 
 impl From<VmError> for LinuxError {
     fn from(err: VmError) -> Self {
         match err {
-            VmError::BadAddress | VmError::AccessDenied => LinuxError::EFAULT,
-            #[cfg(feature = "alloc")]
+            VmError::BadAddress | VmError::AccessDenied | VmError::BusError => LinuxError::EFAULT,
             VmError::TooLong => LinuxError::E2BIG,
+            VmError::InvalidInput => LinuxError::EINVAL,
         }
     }
 }
@@ -53,35 +65,85 @@ pub unsafe trait VmIo {
     fn new() -> Self;
 
     /// Reads data from the virtual memory starting at `start` into `buf`.
+    ///
+    /// Callers in this crate guarantee that `start + buf.len()` does not
+    /// overflow.
     fn read(&mut self, start: usize, buf: &mut [MaybeUninit<u8>]) -> VmResult;
 
     /// Writes data to the virtual memory starting at `start` from `buf`.
+    ///
+    /// Callers in this crate guarantee that `start + buf.len()` does not
+    /// overflow.
     fn write(&mut self, start: usize, buf: &[u8]) -> VmResult;
 }
 
+/// Checks that the range of `len` bytes starting at `start` does not wrap
+/// around the address space.
+#[inline]
+fn check_range(start: usize, len: usize) -> VmResult {
+    match start.checked_add(len) {
+        Some(_) => Ok(()),
+        None => Err(VmError::BadAddress),
+    }
+}
+
 /// Reads a slice from the virtual memory.
 pub fn vm_read_slice<T>(ptr: *const T, buf: &mut [MaybeUninit<T>]) -> VmResult {
     if !ptr.is_aligned() {
         return Err(VmError::BadAddress);
     }
+    check_range(ptr.addr(), size_of_val(buf))?;
     VmImpl::new().read(ptr.addr(), buf.as_bytes_mut())
 }
 
 /// Writes data to the virtual memory.
-pub fn vm_write_slice<T>(ptr: *mut T, buf: &[T]) -> VmResult {
+///
+/// `T` must be [`NoUninit`] so that padding bytes, which may hold stale kernel
+/// data, can never be copied out.
+pub fn vm_write_slice<T: NoUninit>(ptr: *mut T, buf: &[T]) -> VmResult {
     if !ptr.is_aligned() {
         return Err(VmError::BadAddress);
     }
-    // SAFETY: we don't care about validity, since these bytes are only used for
-    // writing to the virtual memory.
-    let bytes = unsafe { slice::from_raw_parts(buf.as_ptr().cast::<u8>(), size_of_val(buf)) };
-    VmImpl::new().write(ptr.addr(), bytes)
+    check_range(ptr.addr(), size_of_val(buf))?;
+    VmImpl::new().write(ptr.addr(), cast_slice(buf))
 }
 
 mod thin;
 pub use thin::{VmMutPtr, VmPtr};
 
+mod blob;
+pub use blob::{vm_read_blob, vm_write_blob};
+
+mod extensible;
+pub use extensible::{MAX_EXTENSIBLE_SIZE, vm_read_extensible, vm_write_extensible};
+
 #[cfg(feature = "alloc")]
 mod alloc;
 #[cfg(feature = "alloc")]
-pub use alloc::{vm_load, vm_load_any, vm_load_c_string, vm_load_until_nul};
+pub use alloc::{
+    vm_load, vm_load_any, vm_load_bounded, vm_load_c_string, vm_load_c_string_array,
+    vm_load_c_string_bounded, vm_load_string, vm_load_until_nul, vm_load_until_nul_bounded,
+};
+
+#[cfg(feature = "dump")]
+mod dump;
+#[cfg(feature = "dump")]
+pub use dump::vm_dump_hex;
+
+#[cfg(feature = "alloc")]
+mod iovec;
+#[cfg(feature = "alloc")]
+pub use iovec::{IoVec, UIO_MAXIOV, VmIoVecs};
+
+#[cfg(feature = "alloc")]
+mod transaction;
+#[cfg(feature = "alloc")]
+pub use transaction::{PreparedWrites, VmTransaction};
+
+/// Re-exports of the pointer traits and error types.
+///
+/// Only items that almost every user of the crate needs belong here. Free
+/// functions and helper types are imported explicitly.
+pub mod prelude {
+    pub use crate::{VmError, VmMutPtr, VmPtr, VmResult};
+}
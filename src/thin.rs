@@ -1,6 +1,6 @@
 use core::{mem::MaybeUninit, ptr::NonNull, slice};
 
-use bytemuck::AnyBitPattern;
+use bytemuck::{AnyBitPattern, NoUninit};
 
 use crate::{VmResult, vm_read_slice, vm_write_slice};
 
@@ -39,6 +39,18 @@ pub trait VmPtr: Copy {
         // SAFETY: `AnyBitPattern`
         Ok(unsafe { uninit.assume_init() })
     }
+
+    /// Reads an array of `N` consecutive values starting at this virtual
+    /// memory pointer.
+    fn vm_read_array<const N: usize>(self) -> VmResult<[Self::Target; N]>
+    where
+        Self::Target: AnyBitPattern,
+    {
+        let mut uninit = MaybeUninit::<[Self::Target; N]>::uninit();
+        vm_read_slice(self.as_ptr().cast(), slice::from_mut(&mut uninit))?;
+        // SAFETY: `AnyBitPattern`
+        Ok(unsafe { uninit.assume_init() })
+    }
 }
 
 impl<T> VmPtr for *const T {
@@ -68,9 +80,21 @@ impl<T> VmPtr for NonNull<T> {
 /// A mutable virtual memory pointer.
 pub trait VmMutPtr: VmPtr {
     /// Overwrites a virtual memory location with the given value.
-    fn vm_write(self, value: Self::Target) -> VmResult {
+    fn vm_write(self, value: Self::Target) -> VmResult
+    where
+        Self::Target: NoUninit,
+    {
         vm_write_slice(self.as_ptr().cast_mut(), slice::from_ref(&value))
     }
+
+    /// Overwrites `N` consecutive virtual memory locations starting at this
+    /// pointer with the given array.
+    fn vm_write_array<const N: usize>(self, value: &[Self::Target; N]) -> VmResult
+    where
+        Self::Target: NoUninit,
+    {
+        vm_write_slice(self.as_ptr().cast_mut(), value)
+    }
 }
 
 impl<T> VmMutPtr for *mut T {}
@@ -0,0 +1,112 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use bytemuck::{NoUninit, cast_slice};
+
+use crate::{VmError, VmImpl, VmIo, VmResult, check_range};
+
+/// A set of writes to the virtual memory that are staged and then applied
+/// together.
+///
+/// Writes are only staged by [`VmTransaction::write`] and
+/// [`VmTransaction::write_slice`]. Before anything is written, the previous
+/// content of every target region is saved. If a write then fails halfway, the
+/// regions that have already been written are restored from the saved content.
+/// The restore is best-effort: if it fails as well, the error is ignored and
+/// the regions may be left partially updated.
+///
+/// Restoring writes back the content saved before the transaction was
+/// applied, so it overwrites any change made to those regions in the meantime,
+/// e.g. a futex word concurrently updated by user space.
+///
+/// Reads are not staged: they have no effect that would need to be rolled
+/// back, so callers can read with [`vm_read_slice`](crate::vm_read_slice) or
+/// [`VmPtr::vm_read`](crate::VmPtr::vm_read) before building the transaction.
+#[derive(Default)]
+pub struct VmTransaction {
+    writes: Vec<(usize, Vec<u8>)>,
+}
+
+impl VmTransaction {
+    /// Creates an empty transaction.
+    pub const fn new() -> Self {
+        Self { writes: Vec::new() }
+    }
+
+    /// Stages a write of `value` to `ptr`.
+    pub fn write<T: NoUninit>(&mut self, ptr: *mut T, value: T) -> VmResult {
+        self.write_slice(ptr, &[value])
+    }
+
+    /// Stages a write of `buf` to the virtual memory starting at `ptr`.
+    pub fn write_slice<T: NoUninit>(&mut self, ptr: *mut T, buf: &[T]) -> VmResult {
+        if !ptr.is_aligned() {
+            return Err(VmError::BadAddress);
+        }
+        check_range(ptr.addr(), size_of_val(buf))?;
+        self.writes.push((ptr.addr(), cast_slice(buf).to_vec()));
+        Ok(())
+    }
+
+    /// Saves the current content of every target region for rollback,
+    /// returning a [`PreparedWrites`] that performs the actual writes.
+    ///
+    /// This only reads the target regions and never writes to them, so it
+    /// fails early on unreadable regions but cannot detect read-only ones.
+    /// Those are only detected by [`PreparedWrites::commit`], which then rolls
+    /// back.
+    pub fn prepare(self) -> VmResult<PreparedWrites> {
+        let mut vm = VmImpl::new();
+
+        let mut backups = Vec::with_capacity(self.writes.len());
+        for (start, data) in &self.writes {
+            let mut backup = Vec::with_capacity(data.len());
+            vm.read(*start, &mut backup.spare_capacity_mut()[..data.len()])?;
+            // SAFETY: The read above initialized `data.len()` bytes.
+            unsafe { backup.set_len(data.len()) };
+            backups.push(backup);
+        }
+
+        Ok(PreparedWrites {
+            writes: self.writes,
+            backups,
+        })
+    }
+
+    /// Applies all staged writes.
+    ///
+    /// This is a shorthand for [`VmTransaction::prepare`] followed by
+    /// [`PreparedWrites::commit`].
+    pub fn commit(self) -> VmResult {
+        self.prepare()?.commit()
+    }
+}
+
+/// Writes whose target regions have been saved by [`VmTransaction::prepare`]
+/// and are ready to be applied.
+///
+/// Dropping it without committing leaves the virtual memory untouched.
+pub struct PreparedWrites {
+    writes: Vec<(usize, Vec<u8>)>,
+    backups: Vec<Vec<u8>>,
+}
+
+impl PreparedWrites {
+    /// Applies all prepared writes.
+    ///
+    /// On failure, the regions that have already been written are restored to
+    /// their previous content on a best-effort basis.
+    pub fn commit(self) -> VmResult {
+        let mut vm = VmImpl::new();
+        for (i, (start, data)) in self.writes.iter().enumerate() {
+            if let Err(err) = vm.write(*start, data) {
+                for ((start, _), backup) in self.writes[..i].iter().zip(&self.backups).rev() {
+                    let _ = vm.write(*start, backup);
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+}
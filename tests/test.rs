@@ -1,13 +1,10 @@
-#![feature(maybe_uninit_slice)]
-#![feature(maybe_uninit_write_slice)]
-
 use std::{
     f32,
     mem::MaybeUninit,
     sync::{LazyLock, Mutex, MutexGuard},
 };
 
-use bytemuck::AnyBitPattern;
+use bytemuck::{AnyBitPattern, NoUninit};
 use extern_trait::extern_trait;
 use starry_vm::{VmError, VmIo, VmMutPtr, VmPtr, VmResult, vm_read_slice, vm_write_slice};
 
@@ -71,23 +68,28 @@ fn test_perm() {
 
 #[test]
 fn test_ptr() {
-    #[derive(Debug, Clone, Copy, PartialEq, AnyBitPattern)]
+    #[derive(Debug, Clone, Copy, PartialEq, AnyBitPattern, NoUninit)]
+    #[repr(C)]
     struct Foo {
         a: i64,
         b: f32,
+        _pad: u32,
     }
 
     const A: Foo = Foo {
         a: 42,
         b: f32::consts::PI,
+        _pad: 0,
     };
     const B: Foo = Foo {
         a: 84,
         b: f32::consts::E,
+        _pad: 0,
     };
     const C: Foo = Foo {
         a: 168,
         b: f32::consts::TAU,
+        _pad: 0,
     };
 
     let ptr = 0x2000 as *mut Foo;
@@ -129,4 +131,309 @@ fn test_load_until_nul() {
 
     vm_write_slice(ptr, &[1; 0x1234]).unwrap();
     assert_eq!(vm_load_until_nul(ptr).unwrap().len(), 0x1234);
+
+    // Odd-sized elements leave a gap before the chunk boundary.
+    let ptr = 0x9ff0 as *mut [u8; 3];
+    vm_write_slice(ptr, &[[1; 3]; 6]).unwrap();
+    assert_eq!(vm_load_until_nul(ptr).unwrap(), [[1; 3]; 6]);
+
+    // Elements larger than a chunk.
+    let ptr = 0xa100 as *mut [[u8; 4096]; 2];
+    assert!(vm_load_until_nul(ptr).unwrap().is_empty());
+    let ptr = 0xa100 as *mut [[[u8; 4096]; 32]; 2];
+    assert!(vm_load_until_nul(ptr).unwrap().is_empty());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_transaction() {
+    use starry_vm::VmTransaction;
+
+    let ptr = 0x6000 as *mut u32;
+
+    let mut tx = VmTransaction::new();
+    tx.write(ptr, 1).unwrap();
+    tx.write_slice(ptr.wrapping_add(1), &[2, 3]).unwrap();
+    tx.commit().unwrap();
+    assert_eq!(ptr.vm_read(), Ok(1));
+    assert_eq!(ptr.wrapping_add(2).vm_read(), Ok(3));
+
+    // 0x100 is readable but not writable, so the commit fails on the last
+    // write and has to roll back the two before it.
+    let mut tx = VmTransaction::new();
+    tx.write(ptr, 4).unwrap();
+    tx.write(ptr.wrapping_add(2), 6).unwrap();
+    tx.write(0x100 as *mut u32, 5).unwrap();
+    let prepared = tx.prepare().unwrap();
+    assert_eq!(prepared.commit(), Err(VmError::AccessDenied));
+    assert_eq!(ptr.vm_read(), Ok(1));
+    assert_eq!(ptr.wrapping_add(1).vm_read(), Ok(2));
+    assert_eq!(ptr.wrapping_add(2).vm_read(), Ok(3));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_prepared_writes() {
+    use starry_vm::VmTransaction;
+
+    let ptr = 0x6100 as *mut u64;
+
+    let mut tx = VmTransaction::new();
+    tx.write(ptr, 42).unwrap();
+    tx.write(0x0200_0000 as *mut u64, 84).unwrap();
+    assert_eq!(tx.prepare().err(), Some(VmError::BadAddress));
+    assert_eq!(ptr.vm_read(), Ok(0));
+
+    let mut tx = VmTransaction::new();
+    tx.write(ptr, 42).unwrap();
+    tx.write(0x100 as *mut u64, 84).unwrap();
+    let prepared = tx.prepare().unwrap();
+    assert_eq!(ptr.vm_read(), Ok(0));
+    assert_eq!(prepared.commit(), Err(VmError::AccessDenied));
+    assert_eq!(ptr.vm_read(), Ok(0));
+
+    let mut tx = VmTransaction::new();
+    tx.write(ptr, 42).unwrap();
+    let prepared = tx.prepare().unwrap();
+    assert_eq!(ptr.vm_read(), Ok(0));
+    prepared.commit().unwrap();
+    assert_eq!(ptr.vm_read(), Ok(42));
+}
+
+#[test]
+#[cfg(feature = "dump")]
+fn test_dump_hex() {
+    use starry_vm::vm_dump_hex;
+
+    let ptr = 0x7000 as *mut u8;
+    vm_write_slice(ptr, b"Hello, world!\n").unwrap();
+
+    let mut out = String::new();
+    vm_dump_hex(ptr.addr(), 20, &mut out).unwrap();
+    assert_eq!(
+        out,
+        "0000000000007000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 00  |Hello, \
+         world!...|\n0000000000007010  00 00 00 00                                       |....|\n"
+    );
+
+    let mut out = String::new();
+    vm_dump_hex(0x00ff_fffc, 6, &mut out).unwrap();
+    assert_eq!(
+        out,
+        "0000000000fffffc  00 00 00 00 ?? ??                                 |....??|\n"
+    );
+
+    let mut out = String::new();
+    vm_dump_hex(usize::MAX - 7, 20, &mut out).unwrap();
+    assert_eq!(
+        out,
+        "fffffffffffffff8  ?? ?? ?? ?? ?? ?? ?? ??  ?? ?? ?? ?? ?? ?? ?? ??  |????????????????|\n"
+    );
+}
+
+#[test]
+fn test_read_extensible() {
+    use starry_vm::{MAX_EXTENSIBLE_SIZE, vm_read_extensible};
+
+    #[derive(Debug, Clone, Copy, PartialEq, AnyBitPattern)]
+    struct Args {
+        flags: u64,
+        size: u64,
+    }
+
+    let ptr = 0x8000 as *mut u64;
+    vm_write_slice(ptr, &[1, 2, 0, 0]).unwrap();
+    let args = ptr.cast::<Args>();
+
+    assert_eq!(
+        vm_read_extensible(args, 8, 8),
+        Ok(Args { flags: 1, size: 0 })
+    );
+    assert_eq!(
+        vm_read_extensible(args, 32, 8),
+        Ok(Args { flags: 1, size: 2 })
+    );
+    assert_eq!(vm_read_extensible(args, 4, 8), Err(VmError::InvalidInput));
+    assert_eq!(
+        vm_read_extensible(args, MAX_EXTENSIBLE_SIZE + 1, 8),
+        Err(VmError::TooLong)
+    );
+
+    ptr.wrapping_add(3).vm_write(3).unwrap();
+    assert_eq!(vm_read_extensible(args, 32, 8), Err(VmError::TooLong));
+}
+
+#[test]
+fn test_write_extensible() {
+    use starry_vm::vm_write_extensible;
+
+    let ptr = 0x8100 as *mut [u32; 4];
+
+    assert_eq!(vm_write_extensible(ptr, 16, [1, 2, 3, 4]), Ok(false));
+    assert_eq!(ptr.vm_read(), Ok([1, 2, 3, 4]));
+
+    assert_eq!(vm_write_extensible(ptr, 8, [5, 6, 7, 8]), Ok(true));
+    assert_eq!(ptr.vm_read(), Ok([5, 6, 3, 4]));
+}
+
+#[test]
+fn test_blob() {
+    use starry_vm::{vm_read_blob, vm_write_blob};
+
+    const DATA: [u8; 64] = [0x5a; 64];
+
+    let ptr = 0x8200 as *mut u8;
+    vm_write_blob(ptr, 64, &DATA).unwrap();
+
+    let mut buf = [MaybeUninit::uninit(); 64];
+    vm_read_blob(ptr, 64, &mut buf).unwrap();
+    assert_eq!(unsafe { buf.assume_init_ref() }, &DATA);
+
+    assert_eq!(
+        vm_write_blob(ptr.wrapping_add(16), 64, &DATA),
+        Err(VmError::BadAddress)
+    );
+    assert_eq!(
+        vm_read_blob(ptr.wrapping_add(16), 64, &mut buf),
+        Err(VmError::BadAddress)
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_load_bounded() {
+    use starry_vm::vm_load_bounded;
+
+    let ptr = 0x8300 as *mut u64;
+    vm_write_slice(ptr, &[1, 2, 3, 4]).unwrap();
+
+    assert_eq!(vm_load_bounded(ptr, 4, 4).unwrap(), [1, 2, 3, 4]);
+    assert_eq!(vm_load_bounded(ptr, 5, 4), Err(VmError::TooLong));
+    assert_eq!(
+        vm_load_bounded(ptr.cast::<u8>().wrapping_add(1).cast::<u64>(), 1, 4),
+        Err(VmError::BadAddress)
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_load_until_nul_bounded() {
+    use starry_vm::{vm_load_c_string_bounded, vm_load_until_nul_bounded};
+
+    let ptr = 0x8400 as *mut u8;
+    vm_write_slice(ptr, b"abcd\0").unwrap();
+
+    assert_eq!(vm_load_until_nul_bounded(ptr, 4).unwrap(), b"abcd");
+    assert_eq!(vm_load_until_nul_bounded(ptr, 3), Err(VmError::TooLong));
+    assert_eq!(
+        vm_load_c_string_bounded(ptr, 4).unwrap().as_bytes(),
+        b"abcd"
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_load_string() {
+    use starry_vm::vm_load_string;
+
+    let ptr = 0x8500 as *mut u8;
+    vm_write_slice(ptr, "héllo\0".as_bytes()).unwrap();
+    assert_eq!(vm_load_string(ptr).unwrap(), "héllo");
+
+    vm_write_slice(ptr, &[0xff, 0xfe, 0]).unwrap();
+    assert_eq!(vm_load_string(ptr), Err(VmError::InvalidInput));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_iovec() {
+    use starry_vm::{IoVec, UIO_MAXIOV, VmIoVecs};
+
+    let ptr = 0x8600 as *mut IoVec;
+    vm_write_slice(
+        ptr,
+        &[
+            IoVec {
+                base: 0x1000,
+                len: 16,
+            },
+            IoVec {
+                base: 0x2000,
+                len: 32,
+            },
+        ],
+    )
+    .unwrap();
+
+    let iovs = VmIoVecs::load(ptr, 2).unwrap();
+    assert_eq!(iovs.len(), 2);
+    assert_eq!(iovs.total_len(), 48);
+    assert_eq!(
+        (&iovs).into_iter().map(|iov| iov.base).collect::<Vec<_>>(),
+        [0x1000, 0x2000]
+    );
+
+    assert_eq!(
+        VmIoVecs::load(ptr, UIO_MAXIOV + 1).err(),
+        Some(VmError::InvalidInput)
+    );
+
+    ptr.wrapping_add(1)
+        .vm_write(IoVec {
+            base: usize::MAX,
+            len: 1,
+        })
+        .unwrap();
+    assert_eq!(VmIoVecs::load(ptr, 2).err(), Some(VmError::BadAddress));
+}
+
+#[test]
+fn test_array() {
+    let ptr = 0x8700 as *mut u16;
+    ptr.vm_write_array(&[1, 2, 3, 4]).unwrap();
+
+    assert_eq!(ptr.vm_read_array(), Ok([1, 2, 3, 4]));
+    assert_eq!(ptr.wrapping_add(2).vm_read_array(), Ok([3, 4]));
+}
+
+#[test]
+fn test_overflow() {
+    let mut buf = [MaybeUninit::uninit(); 2];
+    assert_eq!(
+        vm_read_slice(usize::MAX as *const u8, &mut buf),
+        Err(VmError::BadAddress)
+    );
+    assert_eq!(
+        vm_write_slice((usize::MAX - 1) as *mut u8, &[0; 4]),
+        Err(VmError::BadAddress)
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_load_c_string_array() {
+    use starry_vm::vm_load_c_string_array;
+
+    let strings = 0x8800 as *mut u8;
+    vm_write_slice(strings, b"ls\0-l\0").unwrap();
+
+    let ptr = 0x8900 as *mut usize;
+    vm_write_slice(ptr, &[strings.addr(), strings.addr() + 3, 0]).unwrap();
+    let ptr = ptr.cast::<*const u8>();
+
+    let argv = vm_load_c_string_array(ptr, 2, 6).unwrap();
+    assert_eq!(argv, [c"ls", c"-l"]);
+
+    assert_eq!(vm_load_c_string_array(ptr, 2, 5), Err(VmError::TooLong));
+    assert_eq!(vm_load_c_string_array(ptr, 1, 6), Err(VmError::TooLong));
+
+    // More pointers than fit in the default `vm_load_until_nul` limit.
+    const COUNT: usize = 20000;
+    let ptr = 0x0010_0000 as *mut usize;
+    let mut ptrs = vec![strings.addr(); COUNT];
+    ptrs.push(0);
+    vm_write_slice(ptr, &ptrs).unwrap();
+
+    let argv = vm_load_c_string_array(ptr.cast(), 2, 0x0020_0000).unwrap();
+    assert_eq!(argv.len(), COUNT);
 }